@@ -0,0 +1,88 @@
+//! Raw FFI bindings to the vendored
+//! [C Argon2 reference implementation](https://github.com/P-H-C/phc-winner-argon2).
+//!
+//! Everything in this module is `pub(crate)`; callers interact with the safe
+//! wrappers in [`crate::hasher`] instead.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+use crate::error::Argon2Error;
+
+/// Tells the C library to overwrite `pwd` with zeros once it has been consumed.
+pub(crate) const ARGON2_FLAG_CLEAR_PASSWORD: u32 = 1 << 0;
+/// Tells the C library to overwrite `secret` with zeros once it has been consumed.
+pub(crate) const ARGON2_FLAG_CLEAR_SECRET: u32 = 1 << 1;
+
+/// The C Argon2 variant selector, mirroring `argon2_type` in `argon2.h`.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Argon2Type {
+    Argon2d = 0,
+    Argon2i = 1,
+    Argon2id = 2,
+}
+
+impl From<crate::hasher::Algorithm> for Argon2Type {
+    fn from(algorithm: crate::hasher::Algorithm) -> Self {
+        match algorithm {
+            crate::hasher::Algorithm::Argon2d => Argon2Type::Argon2d,
+            crate::hasher::Algorithm::Argon2i => Argon2Type::Argon2i,
+            crate::hasher::Algorithm::Argon2id => Argon2Type::Argon2id,
+        }
+    }
+}
+
+/// Mirrors the C `argon2_context` struct that `argon2_ctx` reads from and
+/// writes into. Pointers borrow buffers owned by the caller of [`hash_ctx`].
+#[repr(C)]
+pub(crate) struct Argon2Context {
+    pub out: *mut u8,
+    pub outlen: u32,
+    pub pwd: *mut u8,
+    pub pwdlen: u32,
+    pub salt: *mut u8,
+    pub saltlen: u32,
+    pub secret: *mut u8,
+    pub secretlen: u32,
+    pub ad: *mut u8,
+    pub adlen: u32,
+    pub t_cost: u32,
+    pub m_cost: u32,
+    pub lanes: u32,
+    pub threads: u32,
+    pub version: u32,
+    pub allocate_cbk: *const c_void,
+    pub free_cbk: *const c_void,
+    pub flags: u32,
+}
+
+extern "C" {
+    fn argon2_ctx(context: *mut Argon2Context, type_: Argon2Type) -> c_int;
+    fn argon2_error_message(error_code: c_int) -> *const c_char;
+}
+
+/// Runs the C Argon2 reference implementation against a fully populated
+/// [`Argon2Context`], writing the derived bytes to `ctx.out`.
+pub(crate) fn hash_ctx(algorithm: Argon2Type, mut ctx: Argon2Context) -> Result<(), Argon2Error> {
+    let rc = unsafe { argon2_ctx(&mut ctx, algorithm) };
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(error_from_code(rc))
+    }
+}
+
+fn error_from_code(code: c_int) -> Argon2Error {
+    let message = unsafe {
+        let ptr = argon2_error_message(code);
+        if ptr.is_null() {
+            "unknown argon2 error".to_string()
+        } else {
+            std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    };
+    Argon2Error::Argon2 {
+        code: code as i32,
+        message,
+    }
+}
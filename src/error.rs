@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// The error type returned by this crate's fallible operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Argon2Error {
+    /// The underlying C Argon2 library returned a non-zero error code.
+    Argon2 {
+        /// The numeric error code returned by the C library.
+        code: i32,
+        /// The human-readable message the C library associates with `code`.
+        message: String,
+    },
+    /// A PHC-encoded hash string could not be parsed.
+    InvalidHashString(String),
+    /// [`crate::Hasher::derive_key`] was called without a salt set via
+    /// [`crate::Hasher::custom_salt`].
+    MissingSalt,
+}
+
+impl fmt::Display for Argon2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Argon2Error::Argon2 { code, message } => write!(f, "argon2 error {code}: {message}"),
+            Argon2Error::InvalidHashString(reason) => {
+                write!(f, "invalid argon2 hash string: {reason}")
+            }
+            Argon2Error::MissingSalt => {
+                write!(f, "derive_key requires a salt set via Hasher::custom_salt")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Argon2Error {}
@@ -0,0 +1,423 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bindings::{self, Argon2Context, Argon2Type};
+use crate::error::Argon2Error;
+use crate::lexer;
+use crate::zeroize;
+
+/// The Argon2 variant used to compute a hash.
+///
+/// Defaults to [`Algorithm::Argon2id`], the hybrid variant recommended by
+/// the Argon2 RFC for most applications, but a stored hash can declare any
+/// of the three and [`Hash::verify`] honors whichever one it names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// The data-dependent variant. Faster and more GPU-resistant than
+    /// Argon2i, at the cost of exposing password-dependent memory access
+    /// patterns, which makes it unsuitable where side-channel leaks matter.
+    Argon2d,
+    /// The data-independent variant, resistant to side-channel timing
+    /// attacks at some cost to GPU resistance.
+    Argon2i,
+    /// The hybrid variant, combining Argon2i's side-channel resistance with
+    /// Argon2d's resistance to GPU cracking.
+    Argon2id,
+}
+
+impl Algorithm {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Algorithm::Argon2d => "argon2d",
+            Algorithm::Argon2i => "argon2i",
+            Algorithm::Argon2id => "argon2id",
+        }
+    }
+}
+
+impl Default for Algorithm {
+    fn default() -> Self {
+        Algorithm::Argon2id
+    }
+}
+
+/// The Argon2 version used to compute a hash.
+///
+/// A PHC string with no `v=` segment is parsed as [`Version::Version10`],
+/// matching the historical PHC/Argon2 convention (the `v=` segment was
+/// introduced alongside Version13): this is what lets [`Hash::from_str`]
+/// verify legacy hashes produced by other tooling before the versioned
+/// encoding existed.
+///
+/// Known limitation: if a `v=`-less hash was actually produced at
+/// Version13 by something other than this convention (for instance, a
+/// build of this crate predating [`Hasher::version`] that always hashed at
+/// 0x13 without ever writing `v=`), it is still parsed as Version10 and
+/// [`Hash::verify`] will incorrectly reject the correct password. There is
+/// no way to recover the true version from the string alone in that case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// The original, pre-2016 Argon2 version.
+    Version10 = 0x10,
+    /// The current Argon2 version, used unless [`Hasher::version`] overrides it.
+    Version13 = 0x13,
+}
+
+impl Version {
+    pub(crate) fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Version::Version13
+    }
+}
+
+impl std::convert::TryFrom<u32> for Version {
+    type Error = Argon2Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x10 => Ok(Version::Version10),
+            0x13 => Ok(Version::Version13),
+            other => Err(Argon2Error::InvalidHashString(format!(
+                "unsupported argon2 version `{other}`"
+            ))),
+        }
+    }
+}
+
+/// Secret "key" material mixed into a hash in addition to the password.
+///
+/// Unlike the password and the salt, the secret is never stored in the
+/// encoded hash string; the same secret must be supplied again at
+/// verification time via [`Hash::verify_with_secret`].
+#[derive(Clone)]
+pub struct Secret(pub(crate) Vec<u8>);
+
+impl From<&[u8]> for Secret {
+    fn from(bytes: &[u8]) -> Self {
+        Secret(bytes.to_vec())
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// Builds a configured Argon2 hasher.
+///
+/// Construct one with [`Hasher::default`], customize it with its builder
+/// methods, then call [`Hasher::hash`].
+#[derive(Clone)]
+pub struct Hasher {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) memory_cost_kib: u32,
+    pub(crate) time_cost: u32,
+    pub(crate) secret: Option<Secret>,
+    pub(crate) custom_salt: Option<Vec<u8>>,
+    pub(crate) ad: Option<Vec<u8>>,
+    pub(crate) version: Version,
+    pub(crate) parallelism: u32,
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Hasher {
+            algorithm: Algorithm::default(),
+            memory_cost_kib: 19456,
+            time_cost: 2,
+            secret: None,
+            custom_salt: None,
+            ad: None,
+            version: Version::default(),
+            parallelism: 1,
+        }
+    }
+}
+
+impl Hasher {
+    /// Sets the memory cost, in KiB.
+    pub fn memory_cost_kib(&mut self, memory_cost_kib: u32) -> &mut Self {
+        self.memory_cost_kib = memory_cost_kib;
+        self
+    }
+
+    /// Sets the number of iterations over the memory.
+    pub fn time_cost(&mut self, time_cost: u32) -> &mut Self {
+        self.time_cost = time_cost;
+        self
+    }
+
+    /// Sets the Argon2 variant to hash with. Defaults to [`Algorithm::Argon2id`].
+    pub fn algorithm(&mut self, algorithm: Algorithm) -> &mut Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets a secret ("key") to mix into the hash in addition to the password.
+    pub fn secret(&mut self, secret: Secret) -> &mut Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    /// Uses `salt` instead of a randomly generated salt.
+    pub fn custom_salt(&mut self, salt: &[u8]) -> &mut Self {
+        self.custom_salt = Some(salt.to_vec());
+        self
+    }
+
+    /// Binds public associated data (AD) into the hash.
+    ///
+    /// Unlike [`Hasher::secret`], associated data is not secret: it is
+    /// stored alongside the salt and hash in the encoded PHC string, so it
+    /// is best used to domain-separate hashes (e.g. a per-tenant or
+    /// per-purpose tag) rather than to hold key material. Verify with
+    /// [`Hash::verify_with_associated_data`].
+    pub fn associated_data(&mut self, ad: &[u8]) -> &mut Self {
+        self.ad = Some(ad.to_vec());
+        self
+    }
+
+    /// Sets the Argon2 version to hash with. Defaults to [`Version::Version13`].
+    ///
+    /// Use [`Version::Version10`] only to reproduce hashes generated by
+    /// tooling that predates the versioned PHC encoding.
+    pub fn version(&mut self, version: Version) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the number of parallel lanes (and worker threads) to hash with.
+    /// Defaults to `1`.
+    ///
+    /// The C library fills the `p` lanes concurrently using `threads`
+    /// native threads, so raising this on a multi-core machine trades
+    /// memory bandwidth for wall-clock speed without changing the hash's
+    /// memory-hardness.
+    pub fn parallelism(&mut self, parallelism: u32) -> &mut Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Hashes `password`, producing a PHC-encodable [`Hash`].
+    pub fn hash<const SALT_LEN: usize, const HASH_LEN: usize>(
+        &self,
+        password: &[u8],
+    ) -> Result<Hash<SALT_LEN, HASH_LEN>, Argon2Error> {
+        let salt = self.resolve_salt::<SALT_LEN>();
+        let hash = self.hash_raw::<HASH_LEN>(password, &salt)?;
+
+        Ok(Hash {
+            algorithm: self.algorithm,
+            version: self.version,
+            memory_cost_kib: self.memory_cost_kib,
+            time_cost: self.time_cost,
+            parallelism: self.parallelism,
+            salt,
+            hash,
+            ad: self.ad.clone(),
+        })
+    }
+
+    /// Derives `out.len()` bytes of raw key material from `password`, for use
+    /// as e.g. a symmetric encryption key, rather than as a PHC-encoded
+    /// password hash.
+    ///
+    /// Unlike [`Hasher::hash`], this does not generate or encode a salt:
+    /// callers must set one with [`Hasher::custom_salt`] and persist it
+    /// themselves, since it cannot be recovered from `out` alone.
+    pub fn derive_key(&self, password: &[u8], out: &mut [u8]) -> Result<(), Argon2Error> {
+        let salt = self.custom_salt.as_deref().ok_or(Argon2Error::MissingSalt)?;
+        self.hash_into(password, salt, out)
+    }
+
+    fn resolve_salt<const SALT_LEN: usize>(&self) -> [u8; SALT_LEN] {
+        let mut salt = [0u8; SALT_LEN];
+        match &self.custom_salt {
+            Some(custom) => {
+                let len = custom.len().min(SALT_LEN);
+                salt[..len].copy_from_slice(&custom[..len]);
+            }
+            None => getrandom::getrandom(&mut salt).expect("failed to read system RNG"),
+        }
+        salt
+    }
+
+    pub(crate) fn hash_raw<const HASH_LEN: usize>(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+    ) -> Result<[u8; HASH_LEN], Argon2Error> {
+        let mut out = [0u8; HASH_LEN];
+        self.hash_into(password, salt, &mut out)?;
+        Ok(out)
+    }
+
+    /// Runs Argon2 in raw mode, writing `out.len()` bytes of derived key
+    /// material into `out`. Shared by [`Hasher::hash`] (via [`Hasher::hash_raw`])
+    /// and [`Hasher::derive_key`].
+    fn hash_into(&self, password: &[u8], salt: &[u8], out: &mut [u8]) -> Result<(), Argon2Error> {
+        let mut pwd = password.to_vec();
+        let mut salt_buf = salt.to_vec();
+        let mut secret_buf = self.secret.as_ref().map(|secret| secret.0.clone());
+        let (secret_ptr, secret_len) = match &mut secret_buf {
+            Some(buf) => (buf.as_mut_ptr(), buf.len() as u32),
+            None => (std::ptr::null_mut(), 0),
+        };
+        let mut ad_buf = self.ad.clone();
+        let (ad_ptr, ad_len) = match &mut ad_buf {
+            Some(buf) => (buf.as_mut_ptr(), buf.len() as u32),
+            None => (std::ptr::null_mut(), 0),
+        };
+
+        let ctx = Argon2Context {
+            out: out.as_mut_ptr(),
+            outlen: out.len() as u32,
+            pwd: pwd.as_mut_ptr(),
+            pwdlen: pwd.len() as u32,
+            salt: salt_buf.as_mut_ptr(),
+            saltlen: salt_buf.len() as u32,
+            secret: secret_ptr,
+            secretlen: secret_len,
+            ad: ad_ptr,
+            adlen: ad_len,
+            t_cost: self.time_cost,
+            m_cost: self.memory_cost_kib,
+            lanes: self.parallelism,
+            threads: self.parallelism,
+            version: self.version.as_u32(),
+            allocate_cbk: std::ptr::null(),
+            free_cbk: std::ptr::null(),
+            flags: bindings::ARGON2_FLAG_CLEAR_PASSWORD | bindings::ARGON2_FLAG_CLEAR_SECRET,
+        };
+
+        let result = bindings::hash_ctx(Argon2Type::from(self.algorithm), ctx);
+
+        // The C library honors `ARGON2_FLAG_CLEAR_{PASSWORD,SECRET}` itself,
+        // but `ad` has no such flag and our local copies must be wiped
+        // regardless of whether `argon2_ctx` succeeded.
+        zeroize::zeroize(&mut pwd);
+        zeroize::zeroize(&mut salt_buf);
+        if let Some(buf) = &mut secret_buf {
+            zeroize::zeroize(buf);
+        }
+        if let Some(buf) = &mut ad_buf {
+            zeroize::zeroize(buf);
+        }
+
+        result
+    }
+}
+
+/// An Argon2 hash, parameterized by its salt and output length in bytes.
+///
+/// A `Hash` round-trips through its PHC string encoding via [`ToString`]
+/// (through its [`fmt::Display`] impl) and [`FromStr`].
+#[derive(Clone)]
+pub struct Hash<const SALT_LEN: usize, const HASH_LEN: usize> {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) version: Version,
+    pub(crate) memory_cost_kib: u32,
+    pub(crate) time_cost: u32,
+    pub(crate) parallelism: u32,
+    pub(crate) salt: [u8; SALT_LEN],
+    pub(crate) hash: [u8; HASH_LEN],
+    pub(crate) ad: Option<Vec<u8>>,
+}
+
+impl<const SALT_LEN: usize, const HASH_LEN: usize> Drop for Hash<SALT_LEN, HASH_LEN> {
+    fn drop(&mut self) {
+        // The salt and hash bytes stay readable through `verify`/`Display`
+        // for the lifetime of this `Hash`; only scrub them once it's dropped.
+        zeroize::zeroize(&mut self.salt);
+        zeroize::zeroize(&mut self.hash);
+        if let Some(ad) = &mut self.ad {
+            zeroize::zeroize(ad);
+        }
+    }
+}
+
+impl<const SALT_LEN: usize, const HASH_LEN: usize> Hash<SALT_LEN, HASH_LEN> {
+    /// Verifies `password` against this hash.
+    pub fn verify(&self, password: &[u8]) -> bool {
+        self.verify_inner(password, None, self.ad.clone())
+    }
+
+    /// Verifies `password`, mixing in `secret` the same way it was mixed in
+    /// when this hash was produced.
+    pub fn verify_with_secret(&self, password: &[u8], secret: Secret) -> bool {
+        self.verify_inner(password, Some(secret), self.ad.clone())
+    }
+
+    /// Verifies `password`, mixing in `ad` the same way associated data was
+    /// mixed in when this hash was produced via [`Hasher::associated_data`].
+    ///
+    /// This is equivalent to [`Hash::verify`] when the hash was parsed from
+    /// a PHC string that already carries its own `data=` segment and `ad`
+    /// equals that stored data; otherwise `ad` replaces the stored data.
+    pub fn verify_with_associated_data(&self, password: &[u8], ad: &[u8]) -> bool {
+        self.verify_inner(password, None, Some(ad.to_vec()))
+    }
+
+    /// The Argon2 variant this hash was computed with.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn verify_inner(&self, password: &[u8], secret: Option<Secret>, ad: Option<Vec<u8>>) -> bool {
+        let hasher = Hasher {
+            algorithm: self.algorithm,
+            memory_cost_kib: self.memory_cost_kib,
+            time_cost: self.time_cost,
+            secret,
+            custom_salt: Some(self.salt.to_vec()),
+            ad,
+            version: self.version,
+            parallelism: self.parallelism,
+        };
+        match hasher.hash_raw::<HASH_LEN>(password, &self.salt) {
+            Ok(computed) => constant_time_eq(&computed, &self.hash),
+            Err(_) => false,
+        }
+    }
+}
+
+impl<const SALT_LEN: usize, const HASH_LEN: usize> fmt::Display for Hash<SALT_LEN, HASH_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        lexer::encode(
+            f,
+            self.algorithm,
+            self.version,
+            self.memory_cost_kib,
+            self.time_cost,
+            self.parallelism,
+            self.ad.as_deref(),
+            &self.salt,
+            &self.hash,
+        )
+    }
+}
+
+impl<const SALT_LEN: usize, const HASH_LEN: usize> FromStr for Hash<SALT_LEN, HASH_LEN> {
+    type Err = Argon2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lexer::parse(s)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
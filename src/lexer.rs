@@ -0,0 +1,140 @@
+//! Encoding and decoding of the PHC-style `$argon2id$v=19$m=...,t=...,p=...$salt$hash`
+//! strings produced by [`crate::Hash`]'s [`std::fmt::Display`] impl and
+//! consumed by its [`std::str::FromStr`] impl.
+
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine as _};
+
+use crate::error::Argon2Error;
+use crate::hasher::{Algorithm, Hash, Version};
+
+pub(crate) fn encode<const SALT_LEN: usize, const HASH_LEN: usize>(
+    f: &mut fmt::Formatter<'_>,
+    algorithm: Algorithm,
+    version: Version,
+    memory_cost_kib: u32,
+    time_cost: u32,
+    parallelism: u32,
+    ad: Option<&[u8]>,
+    salt: &[u8; SALT_LEN],
+    hash: &[u8; HASH_LEN],
+) -> fmt::Result {
+    write!(
+        f,
+        "${}$v={}$m={},t={},p={}",
+        algorithm.as_str(),
+        version.as_u32(),
+        memory_cost_kib,
+        time_cost,
+        parallelism,
+    )?;
+    if let Some(ad) = ad {
+        write!(f, ",data={}", STANDARD_NO_PAD.encode(ad))?;
+    }
+    write!(
+        f,
+        "${}${}",
+        STANDARD_NO_PAD.encode(salt),
+        STANDARD_NO_PAD.encode(hash),
+    )
+}
+
+pub(crate) fn parse<const SALT_LEN: usize, const HASH_LEN: usize>(
+    s: &str,
+) -> Result<Hash<SALT_LEN, HASH_LEN>, Argon2Error> {
+    let mut parts = s.split('$').filter(|p| !p.is_empty());
+
+    let algorithm = match parts.next() {
+        Some("argon2d") => Algorithm::Argon2d,
+        Some("argon2i") => Algorithm::Argon2i,
+        Some("argon2id") => Algorithm::Argon2id,
+        Some(other) => {
+            return Err(Argon2Error::InvalidHashString(format!(
+                "unsupported algorithm `{other}`"
+            )))
+        }
+        None => return Err(Argon2Error::InvalidHashString("missing algorithm".into())),
+    };
+
+    let next = parts
+        .next()
+        .ok_or_else(|| Argon2Error::InvalidHashString("missing parameters".into()))?;
+
+    // No `v=` segment follows the historical PHC/Argon2 convention of
+    // Version10 (see `Version`'s docs for the interop limitation this implies).
+    let (version, params) = match next.strip_prefix("v=") {
+        Some(v) => (Version::try_from(parse_u32("v", v)?)?, parts
+            .next()
+            .ok_or_else(|| Argon2Error::InvalidHashString("missing parameters".into()))?),
+        None => (Version::Version10, next),
+    };
+
+    let mut memory_cost_kib = None;
+    let mut time_cost = None;
+    let mut parallelism = None;
+    let mut ad = None;
+    for kv in params.split(',') {
+        let (key, value) = kv.split_once('=').ok_or_else(|| {
+            Argon2Error::InvalidHashString(format!("malformed parameter `{kv}`"))
+        })?;
+        match key {
+            "m" => memory_cost_kib = Some(parse_u32(key, value)?),
+            "t" => time_cost = Some(parse_u32(key, value)?),
+            "p" => parallelism = Some(parse_u32(key, value)?),
+            "data" => {
+                ad = Some(STANDARD_NO_PAD.decode(value).map_err(|e| {
+                    Argon2Error::InvalidHashString(format!("invalid associated data encoding: {e}"))
+                })?)
+            }
+            other => {
+                return Err(Argon2Error::InvalidHashString(format!(
+                    "unknown parameter `{other}`"
+                )))
+            }
+        }
+    }
+
+    let salt_b64 = parts
+        .next()
+        .ok_or_else(|| Argon2Error::InvalidHashString("missing salt".into()))?;
+    let hash_b64 = parts
+        .next()
+        .ok_or_else(|| Argon2Error::InvalidHashString("missing hash".into()))?;
+
+    let salt_bytes = STANDARD_NO_PAD
+        .decode(salt_b64)
+        .map_err(|e| Argon2Error::InvalidHashString(format!("invalid salt encoding: {e}")))?;
+    let hash_bytes = STANDARD_NO_PAD
+        .decode(hash_b64)
+        .map_err(|e| Argon2Error::InvalidHashString(format!("invalid hash encoding: {e}")))?;
+
+    let salt: [u8; SALT_LEN] = salt_bytes
+        .try_into()
+        .map_err(|_| Argon2Error::InvalidHashString("salt length mismatch".into()))?;
+    let hash: [u8; HASH_LEN] = hash_bytes
+        .try_into()
+        .map_err(|_| Argon2Error::InvalidHashString("hash length mismatch".into()))?;
+
+    Ok(Hash {
+        algorithm,
+        version,
+        memory_cost_kib: memory_cost_kib
+            .ok_or_else(|| Argon2Error::InvalidHashString("missing `m`".into()))?,
+        time_cost: time_cost
+            .ok_or_else(|| Argon2Error::InvalidHashString("missing `t`".into()))?,
+        // Hashes produced before this crate supported configurable
+        // parallelism have no `p=` segment; they were always hashed with a
+        // single lane, so default to that rather than rejecting them.
+        parallelism: parallelism.unwrap_or(1),
+        salt,
+        hash,
+        ad,
+    })
+}
+
+fn parse_u32(key: &str, value: &str) -> Result<u32, Argon2Error> {
+    value
+        .parse()
+        .map_err(|_| Argon2Error::InvalidHashString(format!("invalid value for `{key}`")))
+}
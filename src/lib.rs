@@ -70,6 +70,41 @@
 //! assert!(hash.verify_with_secret(password, secret.into()));
 //! ```
 //!
+//! Hash a password with a specific Argon2 variant, then recover it after
+//! parsing the hash back from its PHC string:
+//!
+//! ```rust
+//! use argon2_kdf::{Algorithm, Hasher, Hash};
+//! use std::str::FromStr;
+//!
+//! let password = b"password";
+//! let hash: Hash<16, 32> = Hasher::default()
+//!     .algorithm(Algorithm::Argon2i)
+//!     .hash(password)
+//!     .unwrap();
+//! let hash_string = hash.to_string();
+//! let hash: Hash<16, 32> = Hash::from_str(&hash_string).unwrap();
+//! assert_eq!(hash.algorithm(), Algorithm::Argon2i);
+//! assert!(hash.verify(password));
+//! ```
+//!
+//! Hash a password with associated data, then verify the hash:
+//!
+//! ```rust
+//! use argon2_kdf::{Hasher, Hash};
+//! use std::str::FromStr;
+//!
+//! let password = b"password";
+//! let ad = b"tenant-42";
+//! let hash: Hash<16, 32> = Hasher::default()
+//!     .associated_data(ad)
+//!     .hash(password)
+//!     .unwrap();
+//! let hash_string = hash.to_string();
+//! let hash: Hash<16, 32> = Hash::from_str(&hash_string).unwrap();
+//! assert!(hash.verify_with_associated_data(password, ad));
+//! ```
+//!
 //! Hash a password with a custom salt, then verify the hash:
 //!
 //! ```rust
@@ -83,11 +118,73 @@
 //!     .unwrap();
 //! assert!(hash.verify(password));
 //! ```
+//!
+//! Hash a password with an explicit Argon2 version, then verify the hash:
+//!
+//! ```rust
+//! use argon2_kdf::{Hasher, Hash, Version};
+//!
+//! let password = b"password";
+//! let hash: Hash<16, 32> = Hasher::default()
+//!     .version(Version::Version13)
+//!     .hash(password)
+//!     .unwrap();
+//! assert!(hash.to_string().contains("$v=19$"));
+//! assert!(hash.verify(password));
+//! ```
+//!
+//! A hash string with no `v=` segment follows the historical PHC/Argon2
+//! convention of `Version10`, which lets legacy hashes produced by other
+//! tooling before the versioned encoding existed still verify correctly:
+//!
+//! ```rust
+//! use argon2_kdf::{Hasher, Hash, Version};
+//! use std::str::FromStr;
+//!
+//! let password = b"password";
+//! let hash: Hash<16, 32> = Hasher::default()
+//!     .version(Version::Version10)
+//!     .hash(password)
+//!     .unwrap();
+//! let legacy_hash_string = hash.to_string().replacen("$v=16", "", 1);
+//! let legacy_hash: Hash<16, 32> = Hash::from_str(&legacy_hash_string).unwrap();
+//! assert!(legacy_hash.verify(password));
+//! ```
+//!
+//! A hash string with no `p=` segment predates this crate's configurable
+//! parallelism; it is treated as the single-lane default and still
+//! verifies correctly:
+//!
+//! ```rust
+//! use argon2_kdf::{Hasher, Hash};
+//! use std::str::FromStr;
+//!
+//! let password = b"password";
+//! let hash: Hash<16, 32> = Hasher::default().hash(password).unwrap();
+//! let legacy_hash_string = hash.to_string().replacen(",p=1", "", 1);
+//! let legacy_hash: Hash<16, 32> = Hash::from_str(&legacy_hash_string).unwrap();
+//! assert!(legacy_hash.verify(password));
+//! ```
+//!
+//! Derive a raw encryption key from a password, instead of a password hash:
+//!
+//! ```rust
+//! use argon2_kdf::Hasher;
+//!
+//! let password = b"password";
+//! let salt = b"customsalt";
+//! let mut key = [0u8; 32];
+//! Hasher::default()
+//!     .custom_salt(salt)
+//!     .derive_key(password, &mut key)
+//!     .unwrap();
+//! ```
 
 mod bindings;
 mod error;
 mod hasher;
 mod lexer;
+mod zeroize;
 
 pub use error::Argon2Error;
-pub use hasher::{Algorithm, Hash, Hasher, Secret};
+pub use hasher::{Algorithm, Hash, Hasher, Secret, Version};
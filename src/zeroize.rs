@@ -0,0 +1,14 @@
+//! A small memory-hygiene helper used to scrub password, secret, and hash
+//! buffers once they are no longer needed, so they don't linger readable in
+//! freed memory.
+
+/// Overwrites `buf` with zeros using a volatile write, so the optimizer
+/// cannot prove the write is dead and elide it (as it could with a plain
+/// `buf.fill(0)` right before the buffer is dropped).
+pub(crate) fn zeroize(buf: &mut [u8]) {
+    for byte in buf {
+        // SAFETY: `byte` is a valid, aligned `&mut u8` for the duration of the write.
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+}